@@ -4,12 +4,27 @@
 //! fixed locations in space, connected by straight-line paths.
 //!
 //! - Locations live in 3D Euclidean space (`Point3`).
-//! - Connections are edges between locations; their cost is derived
-//!   solely from the coordinates of the endpoints.
-//! - The main entry point is `SpaceNetwork::shortest_route`.
+//! - Connections are edges between locations; their base cost is derived
+//!   from the coordinates of the endpoints, scaled by a per-edge weight
+//!   (default `1.0`) so callers can bias routing without moving locations.
+//! - The main entry point is `SpaceNetwork::shortest_route`, with an
+//!   A*-guided variant (`shortest_route_astar`) for large networks.
+//! - `SpaceNetwork::connect_within_range` and `SpaceNetwork::nearest` use an
+//!   internal k-d tree to answer proximity queries without an O(n^2) scan.
+//! - `SpaceNetwork::best_tour` finds the cheapest order to visit a set of
+//!   waypoints, for "start here, hit these stops, end there" itineraries.
+//! - `SpaceNetwork::k_shortest_routes` returns alternates to the best route,
+//!   for comparing options or routing around a congested hop.
+//! - `SpaceNetwork::all_pairs_distances`, `closeness_centrality`, and
+//!   `degree` give a small graph-analytics surface on top of routing.
+//! - `SpaceNetwork::constrained_route` finds the cheapest route that
+//!   respects a per-hop jump range and, optionally, a hop-count budget.
 
-use std::cmp::Ordering;
-use std::collections::BinaryHeap;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashSet};
+
+mod spatial;
+use spatial::KdTree;
 
 // Wrapper to make `f64` usable in a `BinaryHeap` as an ordered key.
 //
@@ -50,6 +65,57 @@ impl PartialOrd for State {
     }
 }
 
+/// Internal state for [`SpaceNetwork::constrained_route`]'s priority queue,
+/// which expands Dijkstra's state from just `position` to `(position,
+/// hops)` so a hop budget can be enforced.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct HopState {
+    cost: OrderedFloat,
+    position: LocationId,
+    hops: usize,
+}
+
+impl Ord for HopState {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .cost
+            .cmp(&self.cost)
+            .then_with(|| self.position.0.cmp(&other.position.0))
+            .then_with(|| self.hops.cmp(&other.hops))
+    }
+}
+
+impl PartialOrd for HopState {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Wraps a candidate [`Route`] so Yen's algorithm can order it by
+/// `total_distance` in a `BinaryHeap`.
+#[derive(Clone, Debug)]
+struct YenCandidate(Route);
+
+impl PartialEq for YenCandidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.total_distance == other.0.total_distance
+    }
+}
+
+impl Eq for YenCandidate {}
+
+impl PartialOrd for YenCandidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for YenCandidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        OrderedFloat(self.0.total_distance).cmp(&OrderedFloat(other.0.total_distance))
+    }
+}
+
 /// Identifier for a location inside a [`SpaceNetwork`].
 ///
 /// This is intentionally opaque; callers should treat it as a handle
@@ -126,15 +192,24 @@ impl Route {
     }
 }
 
+/// An adjacency list entry: a directed hop to `to`, costing `weight` times
+/// the straight-line distance between its endpoints.
+#[derive(Clone, Copy, Debug)]
+struct Edge {
+    to: LocationId,
+    weight: f64,
+}
+
 /// A network of locations connected by edges.
 ///
-/// Distances along edges are computed as straight-line distances between
-/// the locations' coordinates. Edges themselves do not carry any extra
-/// attributes.
+/// Base distances along edges are straight-line distances between the
+/// locations' coordinates; each edge also carries a cost multiplier
+/// (`1.0` by default) so callers can bias routing without moving the
+/// locations themselves — see [`SpaceNetwork::connect_weighted`].
 #[derive(Clone, Debug)]
 pub struct SpaceNetwork {
     locations: Vec<Location>,
-    adjacency: Vec<Vec<LocationId>>,
+    adjacency: Vec<Vec<Edge>>,
 }
 
 impl SpaceNetwork {
@@ -181,7 +256,7 @@ impl SpaceNetwork {
         self.locations.get(id.0)
     }
 
-    /// Connect two locations with an undirected edge.
+    /// Connect two locations with an undirected edge of weight `1.0`.
     ///
     /// Calling this multiple times will create duplicate edges, which are
     /// harmless but unnecessary; callers should typically connect once.
@@ -189,6 +264,18 @@ impl SpaceNetwork {
         &mut self,
         a: LocationId,
         b: LocationId,
+    ) -> Result<(), &'static str> {
+        self.connect_bidirectional_weighted(a, b, 1.0)
+    }
+
+    /// Connect two locations with an undirected edge whose cost is
+    /// `weight` times the straight-line distance between them, in both
+    /// directions.
+    pub fn connect_bidirectional_weighted(
+        &mut self,
+        a: LocationId,
+        b: LocationId,
+        weight: f64,
     ) -> Result<(), &'static str> {
         if !self.is_valid(a) || !self.is_valid(b) {
             return Err("invalid LocationId");
@@ -197,18 +284,34 @@ impl SpaceNetwork {
             return Ok(()); // ignore self-edges
         }
 
-        self.adjacency[a.0].push(b);
-        self.adjacency[b.0].push(a);
+        self.adjacency[a.0].push(Edge { to: b, weight });
+        self.adjacency[b.0].push(Edge { to: a, weight });
         Ok(())
     }
 
-    /// Connect `from` -> `to` with a directed edge.
+    /// Connect `from` -> `to` with a directed edge of weight `1.0`.
     ///
     /// Useful if you later want one-way “hyperspace lanes”.
     pub fn connect_directed(
         &mut self,
         from: LocationId,
         to: LocationId,
+    ) -> Result<(), &'static str> {
+        self.connect_directed_weighted(from, to, 1.0)
+    }
+
+    /// Connect `from` -> `to` with a directed edge whose cost is `weight`
+    /// times the straight-line distance between them.
+    ///
+    /// This is the building block for modeling one-way lanes that are
+    /// faster or slower than their geometric length would suggest: a
+    /// `weight` below `1.0` models a boosted hop (e.g. a hyperspace lane),
+    /// above `1.0` discourages a hazardous edge.
+    pub fn connect_directed_weighted(
+        &mut self,
+        from: LocationId,
+        to: LocationId,
+        weight: f64,
     ) -> Result<(), &'static str> {
         if !self.is_valid(from) || !self.is_valid(to) {
             return Err("invalid LocationId");
@@ -217,10 +320,20 @@ impl SpaceNetwork {
             return Ok(());
         }
 
-        self.adjacency[from.0].push(to);
+        self.adjacency[from.0].push(Edge { to, weight });
         Ok(())
     }
 
+    /// Convenience alias for [`SpaceNetwork::connect_directed_weighted`].
+    pub fn connect_weighted(
+        &mut self,
+        from: LocationId,
+        to: LocationId,
+        multiplier: f64,
+    ) -> Result<(), &'static str> {
+        self.connect_directed_weighted(from, to, multiplier)
+    }
+
     /// Iterate over neighbors of a location, if it exists.
     pub fn neighbors(
         &self,
@@ -229,7 +342,7 @@ impl SpaceNetwork {
         if !self.is_valid(id) {
             return None;
         }
-        Some(self.adjacency[id.0].iter().copied())
+        Some(self.adjacency[id.0].iter().map(|edge| edge.to))
     }
 
     /// Compute the shortest route from `start` to `goal` using Dijkstra's algorithm.
@@ -246,6 +359,47 @@ impl SpaceNetwork {
             return Some(Route::singleton(start));
         }
 
+        let (dist, prev) = self.run_dijkstra(start, Some(goal));
+
+        let goal_dist = dist[goal.0];
+        if goal_dist.is_infinite() {
+            return None;
+        }
+
+        Some(Route::new(Self::reconstruct_path(&prev, goal), goal_dist))
+    }
+
+    /// Single-source Dijkstra from `start`, returning the best known
+    /// distance and predecessor for every node.
+    ///
+    /// If `goal` is given, the search stops as soon as it's popped (the
+    /// usual point-to-point shortcut); otherwise it runs to completion,
+    /// which callers that need distances to several destinations (e.g.
+    /// [`SpaceNetwork::best_tour`]) can reuse instead of re-running
+    /// `shortest_route` once per pair.
+    fn run_dijkstra(
+        &self,
+        start: LocationId,
+        goal: Option<LocationId>,
+    ) -> (Vec<f64>, Vec<Option<LocationId>>) {
+        self.run_dijkstra_filtered(start, goal, &HashSet::new(), &HashSet::new(), None)
+    }
+
+    /// Same as [`SpaceNetwork::run_dijkstra`], but skipping any node in
+    /// `excluded_nodes`, any edge in `excluded_edges` (by node index), and
+    /// (if given) any edge longer than `max_hop`.
+    ///
+    /// Used by [`SpaceNetwork::k_shortest_routes`] to compute spur paths on
+    /// a temporarily pruned copy of the graph without mutating it, and by
+    /// [`SpaceNetwork::constrained_route`] to enforce a per-hop jump range.
+    fn run_dijkstra_filtered(
+        &self,
+        start: LocationId,
+        goal: Option<LocationId>,
+        excluded_nodes: &HashSet<usize>,
+        excluded_edges: &HashSet<(usize, usize)>,
+        max_hop: Option<f64>,
+    ) -> (Vec<f64>, Vec<Option<LocationId>>) {
         let n = self.locations.len();
         let mut dist = vec![f64::INFINITY; n];
         let mut prev: Vec<Option<LocationId>> = vec![None; n];
@@ -267,19 +421,27 @@ impl SpaceNetwork {
             }
 
             // Early exit if we've reached the goal.
-            if position == goal {
+            if Some(position) == goal {
                 break;
             }
 
             // Relax edges.
-            for &neighbor in &self.adjacency[idx] {
+            for &Edge { to: neighbor, weight } in &self.adjacency[idx] {
                 let n_idx = neighbor.0;
 
+                if excluded_nodes.contains(&n_idx) || excluded_edges.contains(&(idx, n_idx)) {
+                    continue;
+                }
+
                 let edge_len = self.locations[idx]
                     .position
                     .distance_to(&self.locations[n_idx].position);
 
-                let next_cost = cost.0 + edge_len;
+                if edge_len > max_hop.unwrap_or(f64::INFINITY) {
+                    continue;
+                }
+
+                let next_cost = cost.0 + edge_len * weight;
 
                 if next_cost < dist[n_idx] {
                     dist[n_idx] = next_cost;
@@ -292,12 +454,12 @@ impl SpaceNetwork {
             }
         }
 
-        let goal_dist = dist[goal.0];
-        if goal_dist.is_infinite() {
-            return None;
-        }
+        (dist, prev)
+    }
 
-        // Reconstruct path from `goal` back to `start`.
+    /// Walk a predecessor map back from `goal` to its source, returning the
+    /// path in source-to-goal order.
+    fn reconstruct_path(prev: &[Option<LocationId>], goal: LocationId) -> Vec<LocationId> {
         let mut ids = Vec::new();
         let mut current = Some(goal);
         while let Some(id) = current {
@@ -305,8 +467,623 @@ impl SpaceNetwork {
             current = prev[id.0];
         }
         ids.reverse();
+        ids
+    }
+
+    /// Connect every location to all others within `range` of it (inclusive),
+    /// like a ship's jump range.
+    ///
+    /// Internally this builds a k-d tree over the current locations and
+    /// does a radius query per location, so it scales far better than a
+    /// naive all-pairs scan on large networks. The index is rebuilt on
+    /// every call, so prefer calling this once after adding locations
+    /// rather than interleaving it with further edits.
+    pub fn connect_within_range(&mut self, range: f64) {
+        let tree = self.build_index();
+
+        for i in 0..self.locations.len() {
+            let id = self.locations[i].id;
+            let neighbors = tree.within_range(self.locations[i].position, range, id);
+            for neighbor in neighbors {
+                // Each unordered pair is found from both ends; only connect
+                // it from the lower index, since `connect_bidirectional`
+                // wires up both directions.
+                if neighbor.0 > id.0 {
+                    self.connect_bidirectional(id, neighbor).unwrap();
+                }
+            }
+        }
+    }
+
+    /// The `k` closest locations to `id`, nearest first.
+    ///
+    /// Returns `None` if `id` is invalid. Backed by the same k-d tree as
+    /// [`SpaceNetwork::connect_within_range`].
+    pub fn nearest(&self, id: LocationId, k: usize) -> Option<Vec<LocationId>> {
+        if !self.is_valid(id) {
+            return None;
+        }
+        let tree = self.build_index();
+        Some(tree.nearest(self.locations[id.0].position, k, id))
+    }
 
-        Some(Route::new(ids, goal_dist))
+    fn build_index(&self) -> KdTree {
+        let points: Vec<(LocationId, Point3)> =
+            self.locations.iter().map(|loc| (loc.id, loc.position)).collect();
+        KdTree::build(&points)
+    }
+
+    /// Compute the shortest route from `start` to `goal` using A* search.
+    ///
+    /// This searches the same graph as [`SpaceNetwork::shortest_route`]
+    /// and returns an identical optimal route, but guides the search with a
+    /// straight-line-distance heuristic to the goal, so it typically expands
+    /// far fewer nodes on large, spatially-spread networks.
+    ///
+    /// The heuristic (Euclidean distance to `goal`) is admissible and
+    /// consistent as long as every edge's cost multiplier is `>= 1.0`: edge
+    /// cost is then never less than the straight-line distance it spans, so
+    /// the heuristic never overestimates the true remaining cost. A
+    /// multiplier below `1.0` (a boosted "fast lane") can make this
+    /// heuristic overestimate and the search suboptimal; prefer
+    /// [`SpaceNetwork::shortest_route`] on networks with such edges.
+    ///
+    /// Returns `None` if no route exists (disconnected components).
+    pub fn shortest_route_astar(&self, start: LocationId, goal: LocationId) -> Option<Route> {
+        if !self.is_valid(start) || !self.is_valid(goal) {
+            return None;
+        }
+        if start == goal {
+            return Some(Route::singleton(start));
+        }
+
+        let n = self.locations.len();
+        let goal_pos = self.locations[goal.0].position;
+        let heuristic = |idx: usize| self.locations[idx].position.distance_to(&goal_pos);
+
+        let mut g_score = vec![f64::INFINITY; n];
+        let mut prev: Vec<Option<LocationId>> = vec![None; n];
+
+        let mut heap = BinaryHeap::new();
+
+        g_score[start.0] = 0.0;
+        heap.push(State {
+            cost: OrderedFloat(heuristic(start.0)),
+            position: start,
+        });
+
+        while let Some(State { position, .. }) = heap.pop() {
+            let idx = position.0;
+
+            // Early exit if we've reached the goal.
+            if position == goal {
+                break;
+            }
+
+            let g = g_score[idx];
+
+            // Relax edges.
+            for &Edge { to: neighbor, weight } in &self.adjacency[idx] {
+                let n_idx = neighbor.0;
+
+                let edge_len = self.locations[idx]
+                    .position
+                    .distance_to(&self.locations[n_idx].position);
+
+                let next_g = g + edge_len * weight;
+
+                if next_g < g_score[n_idx] {
+                    g_score[n_idx] = next_g;
+                    prev[n_idx] = Some(position);
+                    heap.push(State {
+                        cost: OrderedFloat(next_g + heuristic(n_idx)),
+                        position: neighbor,
+                    });
+                }
+            }
+        }
+
+        let goal_dist = g_score[goal.0];
+        if goal_dist.is_infinite() {
+            return None;
+        }
+
+        Some(Route::new(Self::reconstruct_path(&prev, goal), goal_dist))
+    }
+
+    /// Visit every location in `waypoints` starting from `start`, choosing
+    /// the cheapest order, and optionally ending at a fixed `end` location.
+    ///
+    /// Pairwise distances among `{start} ∪ waypoints ∪ {end}` are computed by
+    /// running Dijkstra once from each of those nodes (reusing the `dist`
+    /// array each run already builds) rather than calling `shortest_route`
+    /// for every pair. The ordering over that small complete graph is then
+    /// solved exactly: by brute-force permutation for up to 10 waypoints, or
+    /// Held–Karp dynamic programming over subsets beyond that. The winning
+    /// order is finally stitched back into one [`Route`] by concatenating
+    /// the real leg routes and summing their distances.
+    ///
+    /// Returns `None` if any id is invalid, or if some leg of the winning
+    /// order has no path.
+    pub fn best_tour(
+        &self,
+        start: LocationId,
+        waypoints: &[LocationId],
+        end: Option<LocationId>,
+    ) -> Option<Route> {
+        if !self.is_valid(start) || waypoints.iter().any(|&w| !self.is_valid(w)) {
+            return None;
+        }
+        if let Some(e) = end {
+            if !self.is_valid(e) {
+                return None;
+            }
+        }
+
+        let w = waypoints.len();
+        if w == 0 {
+            return match end {
+                Some(e) => self.shortest_route(start, e),
+                None => Some(Route::singleton(start)),
+            };
+        }
+
+        // Anchors, in a fixed order: start, then each waypoint, then (if
+        // fixed) the end.
+        let mut anchors = Vec::with_capacity(w + 2);
+        anchors.push(start);
+        anchors.extend_from_slice(waypoints);
+        if let Some(e) = end {
+            anchors.push(e);
+        }
+        let n_anchors = anchors.len();
+
+        let mut d = vec![vec![f64::INFINITY; n_anchors]; n_anchors];
+        for i in 0..n_anchors {
+            let (dist, _) = self.run_dijkstra(anchors[i], None);
+            for (j, &anchor) in anchors.iter().enumerate() {
+                d[i][j] = dist[anchor.0];
+            }
+        }
+
+        let start_idx = 0;
+        let end_idx = end.map(|_| n_anchors - 1);
+
+        let (cost, order) = if w <= 10 {
+            Self::best_order_bruteforce(&d, start_idx, w, end_idx)
+        } else {
+            Self::best_order_held_karp(&d, start_idx, w, end_idx)
+        }?;
+
+        if !cost.is_finite() {
+            return None;
+        }
+
+        // Stitch the winning order into one route by concatenating the real
+        // leg routes, dropping the duplicated junction at each boundary.
+        let mut sequence: Vec<LocationId> = vec![start];
+        sequence.extend(order.iter().map(|&wi| waypoints[wi]));
+        if let Some(e) = end {
+            sequence.push(e);
+        }
+
+        let mut locations = Vec::new();
+        let mut total_distance = 0.0;
+        for pair in sequence.windows(2) {
+            let leg = self.shortest_route(pair[0], pair[1])?;
+            if locations.is_empty() {
+                locations.extend(leg.locations);
+            } else {
+                locations.extend(leg.locations.into_iter().skip(1));
+            }
+            total_distance += leg.total_distance;
+        }
+
+        Some(Route::new(locations, total_distance))
+    }
+
+    /// Exact waypoint ordering by enumerating every permutation; only
+    /// practical for small waypoint counts.
+    ///
+    /// `d` is indexed by anchor index (0 = start, `1..=w` = waypoints,
+    /// optionally `w + 1` = the fixed end). Returns the best total cost and
+    /// the winning order as waypoint-local indices (`0..w`).
+    fn best_order_bruteforce(
+        d: &[Vec<f64>],
+        start_idx: usize,
+        w: usize,
+        end_idx: Option<usize>,
+    ) -> Option<(f64, Vec<usize>)> {
+        let mut indices: Vec<usize> = (0..w).collect();
+        let mut best: Option<(f64, Vec<usize>)> = None;
+
+        permute(&mut indices, &mut |order| {
+            let mut cost = d[start_idx][order[0] + 1];
+            for pair in order.windows(2) {
+                cost += d[pair[0] + 1][pair[1] + 1];
+            }
+            if let Some(end_idx) = end_idx {
+                cost += d[*order.last().unwrap() + 1][end_idx];
+            }
+            if best.as_ref().is_none_or(|(best_cost, _)| cost < *best_cost) {
+                best = Some((cost, order.to_vec()));
+            }
+        });
+
+        best
+    }
+
+    /// Exact waypoint ordering via Held–Karp dynamic programming over
+    /// subsets of waypoints; polynomial in `2^w` instead of `w!`.
+    fn best_order_held_karp(
+        d: &[Vec<f64>],
+        start_idx: usize,
+        w: usize,
+        end_idx: Option<usize>,
+    ) -> Option<(f64, Vec<usize>)> {
+        let n_masks = 1usize << w;
+        // dp[mask][j]: cheapest cost to start, cover waypoint set `mask`,
+        // and end at waypoint `j` (only defined where `mask` contains `j`).
+        let mut dp = vec![vec![f64::INFINITY; w]; n_masks];
+        let mut parent = vec![vec![None; w]; n_masks];
+
+        for j in 0..w {
+            dp[1 << j][j] = d[start_idx][j + 1];
+        }
+
+        for mask in 1..n_masks {
+            for j in 0..w {
+                if mask & (1 << j) == 0 || !dp[mask][j].is_finite() {
+                    continue;
+                }
+                for k in 0..w {
+                    if mask & (1 << k) != 0 {
+                        continue;
+                    }
+                    let next_mask = mask | (1 << k);
+                    let candidate = dp[mask][j] + d[j + 1][k + 1];
+                    if candidate < dp[next_mask][k] {
+                        dp[next_mask][k] = candidate;
+                        parent[next_mask][k] = Some(j);
+                    }
+                }
+            }
+        }
+
+        let full_mask = n_masks - 1;
+        let mut best_j = None;
+        let mut best_cost = f64::INFINITY;
+        for j in 0..w {
+            let cost = dp[full_mask][j] + end_idx.map_or(0.0, |e| d[j + 1][e]);
+            if cost < best_cost {
+                best_cost = cost;
+                best_j = Some(j);
+            }
+        }
+
+        let mut order = Vec::with_capacity(w);
+        let mut mask = full_mask;
+        let mut j = best_j?;
+        loop {
+            order.push(j);
+            let prev = parent[mask][j];
+            mask &= !(1 << j);
+            match prev {
+                Some(p) => j = p,
+                None => break,
+            }
+        }
+        order.reverse();
+
+        Some((best_cost, order))
+    }
+
+    /// The `k` shortest loopless routes from `start` to `goal`, in
+    /// nondecreasing order of `total_distance`, via Yen's algorithm.
+    ///
+    /// The first route is the plain Dijkstra shortest path. Each subsequent
+    /// route is found by "spurring" off every node of the previously
+    /// accepted path: the edges and nodes that would recreate an
+    /// already-found path prefix at that spur are temporarily excluded, a
+    /// spur path from there to `goal` is computed on the pruned graph, and
+    /// the root prefix is stitched onto it to form a candidate. Candidates
+    /// are collected in a min-heap keyed by total distance; each round pops
+    /// the cheapest one not already accepted. Returns fewer than `k` routes
+    /// if the graph doesn't have that many distinct loopless paths.
+    pub fn k_shortest_routes(&self, start: LocationId, goal: LocationId, k: usize) -> Vec<Route> {
+        if !self.is_valid(start) || !self.is_valid(goal) || k == 0 {
+            return Vec::new();
+        }
+
+        let Some(first) = self.shortest_route(start, goal) else {
+            return Vec::new();
+        };
+
+        let mut accepted = vec![first];
+        let mut candidates: BinaryHeap<Reverse<YenCandidate>> = BinaryHeap::new();
+        let mut seen: HashSet<Vec<LocationId>> = HashSet::new();
+
+        while accepted.len() < k {
+            let prev_path = accepted.last().unwrap().locations.clone();
+
+            for i in 0..prev_path.len().saturating_sub(1) {
+                let spur_node = prev_path[i];
+                let root_path = &prev_path[..=i];
+
+                // Exclude the edge out of the spur node that any already
+                // accepted route sharing this root prefix used, so the spur
+                // search can't just rediscover it.
+                let mut excluded_edges = HashSet::new();
+                for route in &accepted {
+                    if route.locations.len() > i + 1 && route.locations[..=i] == *root_path {
+                        excluded_edges.insert((route.locations[i].0, route.locations[i + 1].0));
+                    }
+                }
+
+                // Exclude the root path's interior nodes (everything but the
+                // spur itself) so the spur path can't loop back through them.
+                let excluded_nodes: HashSet<usize> =
+                    root_path[..root_path.len() - 1].iter().map(|id| id.0).collect();
+
+                if let Some(spur_route) = self.dijkstra_excluding(
+                    spur_node,
+                    goal,
+                    &excluded_nodes,
+                    &excluded_edges,
+                ) {
+                    let root_distance = self.path_distance(root_path);
+
+                    let mut locations = root_path[..root_path.len() - 1].to_vec();
+                    locations.extend(spur_route.locations);
+
+                    if seen.insert(locations.clone()) {
+                        let total_distance = root_distance + spur_route.total_distance;
+                        candidates.push(Reverse(YenCandidate(Route::new(
+                            locations,
+                            total_distance,
+                        ))));
+                    }
+                }
+            }
+
+            match candidates.pop() {
+                Some(Reverse(YenCandidate(route))) => accepted.push(route),
+                None => break,
+            }
+        }
+
+        accepted
+    }
+
+    /// Sum of the edge costs (cheapest `weight * distance`) between
+    /// consecutive locations in `path`.
+    fn path_distance(&self, path: &[LocationId]) -> f64 {
+        path.windows(2)
+            .map(|pair| self.cheapest_edge_cost(pair[0], pair[1]))
+            .sum()
+    }
+
+    /// The cost of the cheapest edge `from -> to`, i.e. the smallest
+    /// `weight * distance` among any (possibly duplicate) edges between
+    /// them — matching what Dijkstra's relaxation would have picked.
+    fn cheapest_edge_cost(&self, from: LocationId, to: LocationId) -> f64 {
+        let edge_len = self.locations[from.0]
+            .position
+            .distance_to(&self.locations[to.0].position);
+
+        self.adjacency[from.0]
+            .iter()
+            .filter(|edge| edge.to == to)
+            .map(|edge| edge_len * edge.weight)
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    /// Shortest route from `start` to `goal` that avoids `excluded_nodes`
+    /// and `excluded_edges` (by node index), used for Yen's spur search.
+    fn dijkstra_excluding(
+        &self,
+        start: LocationId,
+        goal: LocationId,
+        excluded_nodes: &HashSet<usize>,
+        excluded_edges: &HashSet<(usize, usize)>,
+    ) -> Option<Route> {
+        if start == goal {
+            return Some(Route::singleton(start));
+        }
+
+        let (dist, prev) =
+            self.run_dijkstra_filtered(start, Some(goal), excluded_nodes, excluded_edges, None);
+
+        let goal_dist = dist[goal.0];
+        if goal_dist.is_infinite() {
+            return None;
+        }
+
+        Some(Route::new(Self::reconstruct_path(&prev, goal), goal_dist))
+    }
+
+    /// An `n x n` matrix of shortest-route distances between every pair of
+    /// locations, computed by running Dijkstra from each node in turn.
+    ///
+    /// Entry `[i][j]` is the distance from the location at index `i` to the
+    /// one at index `j` (`0.0` on the diagonal), or `f64::INFINITY` if `j`
+    /// isn't reachable from `i`. For directed graphs this is distance
+    /// *from* `i` *to* `j`, so the matrix need not be symmetric.
+    pub fn all_pairs_distances(&self) -> Vec<Vec<f64>> {
+        (0..self.locations.len())
+            .map(|i| self.run_dijkstra(LocationId(i), None).0)
+            .collect()
+    }
+
+    /// Closeness centrality of every location: `(reachable_count - 1) /
+    /// sum_of_finite_distances`, where `reachable_count` includes the node
+    /// itself.
+    ///
+    /// A node that reaches nothing else (or nothing at all, in a
+    /// single-node network) gets a centrality of `0.0`. For directed
+    /// graphs this uses outbound distances, so it measures how cheaply a
+    /// location can *reach* the rest of the network, not how cheaply it
+    /// can be reached.
+    pub fn closeness_centrality(&self) -> Vec<f64> {
+        self.all_pairs_distances()
+            .iter()
+            .map(|dist| {
+                let reachable_count = dist.iter().filter(|d| d.is_finite()).count();
+                let sum_of_finite_distances: f64 =
+                    dist.iter().filter(|d| d.is_finite()).sum();
+
+                if reachable_count <= 1 || sum_of_finite_distances == 0.0 {
+                    0.0
+                } else {
+                    (reachable_count - 1) as f64 / sum_of_finite_distances
+                }
+            })
+            .collect()
+    }
+
+    /// Out-degree of a location: the number of edges leading out of it.
+    ///
+    /// Returns `None` if `id` is invalid. For a bidirectional edge, both
+    /// endpoints count it once each.
+    pub fn degree(&self, id: LocationId) -> Option<usize> {
+        if !self.is_valid(id) {
+            return None;
+        }
+        Some(self.adjacency[id.0].len())
+    }
+
+    /// Cheapest route from `start` to `goal` that never takes a single hop
+    /// longer than `max_hop`, and (if given) never uses more than
+    /// `max_hops` hops.
+    ///
+    /// Returns `None` if every unconstrained route violates the jump range
+    /// or hop budget, even when an unconstrained `shortest_route` exists.
+    pub fn constrained_route(
+        &self,
+        start: LocationId,
+        goal: LocationId,
+        max_hop: f64,
+        max_hops: Option<usize>,
+    ) -> Option<Route> {
+        if !self.is_valid(start) || !self.is_valid(goal) {
+            return None;
+        }
+        if start == goal {
+            return Some(Route::singleton(start));
+        }
+
+        match max_hops {
+            None => {
+                // No hop-count budget, just a per-hop range limit: an
+                // ordinary filtered Dijkstra run suffices.
+                let (dist, prev) = self.run_dijkstra_filtered(
+                    start,
+                    Some(goal),
+                    &HashSet::new(),
+                    &HashSet::new(),
+                    Some(max_hop),
+                );
+                let goal_dist = dist[goal.0];
+                if goal_dist.is_infinite() {
+                    return None;
+                }
+                Some(Route::new(Self::reconstruct_path(&prev, goal), goal_dist))
+            }
+            Some(max_hops) => self.constrained_route_bounded_hops(start, goal, max_hop, max_hops),
+        }
+    }
+
+    /// [`SpaceNetwork::constrained_route`] with both a per-hop range limit
+    /// and a hard cap on the number of hops.
+    ///
+    /// Dijkstra's state is expanded from just `position` to `(position,
+    /// hops_used)`, with `dist`/`prev` keyed on that pair via a flat
+    /// `Vec` of length `n * (max_hops + 1)`.
+    fn constrained_route_bounded_hops(
+        &self,
+        start: LocationId,
+        goal: LocationId,
+        max_hop: f64,
+        max_hops: usize,
+    ) -> Option<Route> {
+        let width = max_hops + 1;
+        let state_index = |node: usize, hops: usize| node * width + hops;
+
+        let mut dist = vec![f64::INFINITY; self.locations.len() * width];
+        let mut prev: Vec<Option<(LocationId, usize)>> = vec![None; self.locations.len() * width];
+
+        let mut heap = BinaryHeap::new();
+
+        dist[state_index(start.0, 0)] = 0.0;
+        heap.push(HopState {
+            cost: OrderedFloat(0.0),
+            position: start,
+            hops: 0,
+        });
+
+        while let Some(HopState { cost, position, hops }) = heap.pop() {
+            let here = state_index(position.0, hops);
+
+            if cost.0 > dist[here] {
+                continue;
+            }
+
+            // The first time `goal` is popped is the cheapest way to reach
+            // it within the hop budget, regardless of how many hops it took.
+            if position == goal {
+                let path = Self::reconstruct_hop_path(&prev, goal, hops, width);
+                return Some(Route::new(path, cost.0));
+            }
+
+            if hops >= max_hops {
+                continue;
+            }
+
+            for &Edge { to: neighbor, weight } in &self.adjacency[position.0] {
+                let edge_len = self.locations[position.0]
+                    .position
+                    .distance_to(&self.locations[neighbor.0].position);
+
+                if edge_len > max_hop {
+                    continue;
+                }
+
+                let next_hops = hops + 1;
+                let next_cost = cost.0 + edge_len * weight;
+                let next_state = state_index(neighbor.0, next_hops);
+
+                if next_cost < dist[next_state] {
+                    dist[next_state] = next_cost;
+                    prev[next_state] = Some((position, hops));
+                    heap.push(HopState {
+                        cost: OrderedFloat(next_cost),
+                        position: neighbor,
+                        hops: next_hops,
+                    });
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Walk a `(node, hops)`-keyed predecessor map back from `(goal,
+    /// goal_hops)` to its source, returning the path in source-to-goal
+    /// order.
+    fn reconstruct_hop_path(
+        prev: &[Option<(LocationId, usize)>],
+        goal: LocationId,
+        goal_hops: usize,
+        width: usize,
+    ) -> Vec<LocationId> {
+        let mut ids = Vec::new();
+        let mut current = Some((goal, goal_hops));
+        while let Some((id, hops)) = current {
+            ids.push(id);
+            current = prev[id.0 * width + hops];
+        }
+        ids.reverse();
+        ids
     }
 
     fn is_valid(&self, id: LocationId) -> bool {
@@ -314,6 +1091,31 @@ impl SpaceNetwork {
     }
 }
 
+/// Call `visit` once for every permutation of `items`, via Heap's algorithm.
+fn permute<T: Copy>(items: &mut [T], visit: &mut impl FnMut(&[T])) {
+    let n = items.len();
+    if n == 0 {
+        visit(items);
+        return;
+    }
+    permute_recursive(items, n, visit);
+}
+
+fn permute_recursive<T: Copy>(items: &mut [T], k: usize, visit: &mut impl FnMut(&[T])) {
+    if k == 1 {
+        visit(items);
+        return;
+    }
+    for i in 0..k {
+        permute_recursive(items, k - 1, visit);
+        if k.is_multiple_of(2) {
+            items.swap(i, k - 1);
+        } else {
+            items.swap(0, k - 1);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -369,4 +1171,367 @@ mod tests {
         let route = net.shortest_route(a, c);
         assert!(route.is_none());
     }
+
+    #[test]
+    fn astar_matches_dijkstra_on_multi_hop_graph() {
+        let mut net = SpaceNetwork::new();
+        let a = net.add_location(Point3::new(0.0, 0.0, 0.0));
+        let b = net.add_location(Point3::new(9.0, 0.0, 0.0));
+        let c = net.add_location(Point3::new(3.0, 0.0, 0.0));
+        let d = net.add_location(Point3::new(6.0, 0.0, 0.0));
+
+        net.connect_bidirectional(a, b).unwrap(); // direct = 9
+        net.connect_bidirectional(a, c).unwrap(); // 3
+        net.connect_bidirectional(c, d).unwrap(); // 3
+        net.connect_bidirectional(d, b).unwrap(); // 3
+        net.move_location(b, Point3::new(10.0, 0.0, 0.0)).unwrap(); // direct now 10
+
+        let dijkstra = net.shortest_route(a, b).expect("route should exist");
+        let astar = net.shortest_route_astar(a, b).expect("route should exist");
+
+        assert_eq!(astar.locations, dijkstra.locations);
+        assert!(approx_eq(astar.total_distance, dijkstra.total_distance));
+    }
+
+    #[test]
+    fn astar_no_route_in_disconnected_graph() {
+        let mut net = SpaceNetwork::new();
+        let a = net.add_location(Point3::new(0.0, 0.0, 0.0));
+        let b = net.add_location(Point3::new(1.0, 0.0, 0.0));
+        let c = net.add_location(Point3::new(100.0, 0.0, 0.0));
+
+        net.connect_bidirectional(a, b).unwrap();
+        // `c` is isolated
+
+        assert!(net.shortest_route_astar(a, c).is_none());
+    }
+
+    #[test]
+    fn connect_within_range_links_only_nearby_locations() {
+        let mut net = SpaceNetwork::new();
+        let a = net.add_location(Point3::new(0.0, 0.0, 0.0));
+        let b = net.add_location(Point3::new(1.0, 0.0, 0.0)); // within range of a
+        let c = net.add_location(Point3::new(100.0, 0.0, 0.0)); // far away
+
+        net.connect_within_range(5.0);
+
+        assert!(net.neighbors(a).unwrap().any(|n| n == b));
+        assert!(net.neighbors(b).unwrap().any(|n| n == a));
+        assert!(!net.neighbors(a).unwrap().any(|n| n == c));
+        assert!(!net.neighbors(c).unwrap().any(|n| n == a));
+    }
+
+    #[test]
+    fn nearest_returns_k_closest_sorted_by_distance() {
+        let mut net = SpaceNetwork::new();
+        let origin = net.add_location(Point3::new(0.0, 0.0, 0.0));
+        let near = net.add_location(Point3::new(1.0, 0.0, 0.0));
+        let mid = net.add_location(Point3::new(5.0, 0.0, 0.0));
+        let far = net.add_location(Point3::new(50.0, 0.0, 0.0));
+
+        let result = net.nearest(origin, 2).expect("origin is valid");
+        assert_eq!(result, vec![near, mid]);
+        assert!(!result.contains(&far));
+    }
+
+    #[test]
+    fn nearest_is_none_for_invalid_location() {
+        let net = SpaceNetwork::new();
+        let bogus = LocationId(42);
+        assert!(net.nearest(bogus, 1).is_none());
+    }
+
+    #[test]
+    fn best_tour_orders_waypoints_cheaply() {
+        // A square: start at a, must visit c and b, end at d.
+        // Going around the perimeter (a -> b -> c -> d, cost 3) is cheaper
+        // than visiting the waypoints in the given order (c, b), which
+        // costs 2 + 1 + 2 = 5 along the same perimeter edges.
+        let mut net = SpaceNetwork::new();
+        let a = net.add_location(Point3::new(0.0, 0.0, 0.0));
+        let b = net.add_location(Point3::new(1.0, 0.0, 0.0));
+        let c = net.add_location(Point3::new(1.0, 1.0, 0.0));
+        let d = net.add_location(Point3::new(0.0, 1.0, 0.0));
+
+        net.connect_bidirectional(a, b).unwrap();
+        net.connect_bidirectional(b, c).unwrap();
+        net.connect_bidirectional(c, d).unwrap();
+        net.connect_bidirectional(d, a).unwrap();
+
+        let tour = net.best_tour(a, &[c, b], Some(d)).expect("tour should exist");
+        assert_eq!(tour.locations, vec![a, b, c, d]);
+        assert!(approx_eq(tour.total_distance, 3.0));
+    }
+
+    #[test]
+    fn best_tour_open_ended_picks_cheapest_final_stop() {
+        let mut net = SpaceNetwork::new();
+        let start = net.add_location(Point3::new(0.0, 0.0, 0.0));
+        let near = net.add_location(Point3::new(1.0, 0.0, 0.0));
+        let far = net.add_location(Point3::new(5.0, 0.0, 0.0));
+
+        net.connect_bidirectional(start, near).unwrap();
+        net.connect_bidirectional(near, far).unwrap();
+
+        let tour = net
+            .best_tour(start, &[near, far], None)
+            .expect("tour should exist");
+        assert_eq!(tour.locations, vec![start, near, far]);
+        assert!(approx_eq(tour.total_distance, 5.0));
+    }
+
+    #[test]
+    fn best_tour_is_none_for_invalid_waypoint() {
+        let mut net = SpaceNetwork::new();
+        let a = net.add_location(Point3::new(0.0, 0.0, 0.0));
+        let bogus = LocationId(99);
+
+        assert!(net.best_tour(a, &[bogus], None).is_none());
+    }
+
+    #[test]
+    fn k_shortest_routes_in_nondecreasing_order() {
+        // Two parallel chains between a and b of different lengths, plus
+        // a direct edge, so there are three genuinely distinct routes.
+        let mut net = SpaceNetwork::new();
+        let a = net.add_location(Point3::new(0.0, 0.0, 0.0));
+        let b = net.add_location(Point3::new(10.0, 0.0, 0.0));
+        let c = net.add_location(Point3::new(5.0, 1.0, 0.0));
+        let d = net.add_location(Point3::new(5.0, 3.0, 0.0));
+
+        net.connect_bidirectional(a, b).unwrap(); // direct, distance 10
+        net.connect_bidirectional(a, c).unwrap();
+        net.connect_bidirectional(c, b).unwrap();
+        net.connect_bidirectional(a, d).unwrap();
+        net.connect_bidirectional(d, b).unwrap();
+
+        let routes = net.k_shortest_routes(a, b, 3);
+        assert_eq!(routes.len(), 3);
+        for pair in routes.windows(2) {
+            assert!(pair[0].total_distance <= pair[1].total_distance);
+        }
+        // All three routes should be distinct paths.
+        let unique: HashSet<_> = routes.iter().map(|r| r.locations.clone()).collect();
+        assert_eq!(unique.len(), 3);
+    }
+
+    #[test]
+    fn k_shortest_routes_stops_early_when_graph_is_exhausted() {
+        let mut net = SpaceNetwork::new();
+        let a = net.add_location(Point3::new(0.0, 0.0, 0.0));
+        let b = net.add_location(Point3::new(1.0, 0.0, 0.0));
+        net.connect_bidirectional(a, b).unwrap();
+
+        // Only one loopless route exists between a and b.
+        let routes = net.k_shortest_routes(a, b, 5);
+        assert_eq!(routes.len(), 1);
+    }
+
+    #[test]
+    fn k_shortest_routes_empty_when_disconnected() {
+        let mut net = SpaceNetwork::new();
+        let a = net.add_location(Point3::new(0.0, 0.0, 0.0));
+        let b = net.add_location(Point3::new(1.0, 0.0, 0.0));
+        // no connection between a and b
+
+        assert!(net.k_shortest_routes(a, b, 3).is_empty());
+    }
+
+    #[test]
+    fn weighted_lane_beats_equal_length_unweighted_path() {
+        let mut net = SpaceNetwork::new();
+        let a = net.add_location(Point3::new(0.0, 0.0, 0.0));
+        let b = net.add_location(Point3::new(4.0, 0.0, 0.0)); // direct = 4
+        let c = net.add_location(Point3::new(2.0, 0.0, 0.0)); // via c = 4 total too
+
+        net.connect_bidirectional(a, c).unwrap(); // 2
+        net.connect_bidirectional(c, b).unwrap(); // 2
+        // A cheap hyperspace lane straight from a to b: geometrically 4,
+        // but only costs as much as 1 unit of travel.
+        net.connect_weighted(a, b, 0.25).unwrap();
+
+        let route = net.shortest_route(a, b).expect("route should exist");
+        assert_eq!(route.locations, vec![a, b]);
+        assert!(approx_eq(route.total_distance, 1.0));
+    }
+
+    #[test]
+    fn heavy_weight_discourages_an_edge() {
+        let mut net = SpaceNetwork::new();
+        let a = net.add_location(Point3::new(0.0, 0.0, 0.0));
+        let b = net.add_location(Point3::new(1.0, 0.0, 0.0)); // direct = 1, but hazardous
+        let c = net.add_location(Point3::new(0.0, 3.0, 0.0));
+        let d = net.add_location(Point3::new(1.0, 3.0, 0.0));
+
+        net.connect_weighted(a, b, 10.0).unwrap(); // effective cost 10
+        net.connect_bidirectional(a, c).unwrap(); // 3
+        net.connect_bidirectional(c, d).unwrap(); // 1
+        net.connect_bidirectional(d, b).unwrap(); // 3
+
+        let route = net.shortest_route(a, b).expect("route should exist");
+        assert_eq!(route.locations, vec![a, c, d, b]);
+        assert!(approx_eq(route.total_distance, 7.0));
+    }
+
+    #[test]
+    fn connect_directed_and_bidirectional_default_to_unit_weight() {
+        let mut net = SpaceNetwork::new();
+        let a = net.add_location(Point3::new(0.0, 0.0, 0.0));
+        let b = net.add_location(Point3::new(3.0, 4.0, 0.0)); // distance 5
+
+        net.connect_bidirectional(a, b).unwrap();
+
+        let route = net.shortest_route(a, b).expect("route should exist");
+        assert!(approx_eq(route.total_distance, 5.0));
+
+        let mut directed = SpaceNetwork::new();
+        let a = directed.add_location(Point3::new(0.0, 0.0, 0.0));
+        let b = directed.add_location(Point3::new(3.0, 4.0, 0.0)); // distance 5
+
+        directed.connect_directed(a, b).unwrap();
+
+        let route = directed.shortest_route(a, b).expect("route should exist");
+        assert!(approx_eq(route.total_distance, 5.0));
+    }
+
+    #[test]
+    fn all_pairs_distances_matches_shortest_route() {
+        let mut net = SpaceNetwork::new();
+        let a = net.add_location(Point3::new(0.0, 0.0, 0.0));
+        let b = net.add_location(Point3::new(3.0, 0.0, 0.0));
+        let c = net.add_location(Point3::new(3.0, 4.0, 0.0));
+
+        net.connect_bidirectional(a, b).unwrap();
+        net.connect_bidirectional(b, c).unwrap();
+
+        let matrix = net.all_pairs_distances();
+        assert!(approx_eq(matrix[a.0][a.0], 0.0));
+        assert!(approx_eq(matrix[a.0][b.0], 3.0));
+        assert!(approx_eq(matrix[a.0][c.0], 7.0));
+    }
+
+    #[test]
+    fn all_pairs_distances_is_infinite_for_unreachable_pairs() {
+        let mut net = SpaceNetwork::new();
+        let a = net.add_location(Point3::new(0.0, 0.0, 0.0));
+        let b = net.add_location(Point3::new(1.0, 0.0, 0.0));
+        // no connection between a and b
+
+        let matrix = net.all_pairs_distances();
+        assert!(matrix[a.0][b.0].is_infinite());
+    }
+
+    #[test]
+    fn closeness_centrality_ranks_hub_above_leaf() {
+        // Star graph: hub connects to three leaves, which aren't
+        // connected to each other.
+        let mut net = SpaceNetwork::new();
+        let hub = net.add_location(Point3::new(0.0, 0.0, 0.0));
+        let l1 = net.add_location(Point3::new(1.0, 0.0, 0.0));
+        let l2 = net.add_location(Point3::new(0.0, 1.0, 0.0));
+        let l3 = net.add_location(Point3::new(0.0, 0.0, 1.0));
+
+        net.connect_bidirectional(hub, l1).unwrap();
+        net.connect_bidirectional(hub, l2).unwrap();
+        net.connect_bidirectional(hub, l3).unwrap();
+
+        let centrality = net.closeness_centrality();
+        assert!(centrality[hub.0] > centrality[l1.0]);
+    }
+
+    #[test]
+    fn closeness_centrality_is_zero_for_isolated_node() {
+        let mut net = SpaceNetwork::new();
+        let a = net.add_location(Point3::new(0.0, 0.0, 0.0));
+        let _b = net.add_location(Point3::new(1.0, 0.0, 0.0));
+        // a is isolated
+
+        let centrality = net.closeness_centrality();
+        assert!(approx_eq(centrality[a.0], 0.0));
+    }
+
+    #[test]
+    fn degree_counts_outbound_edges() {
+        let mut net = SpaceNetwork::new();
+        let a = net.add_location(Point3::new(0.0, 0.0, 0.0));
+        let b = net.add_location(Point3::new(1.0, 0.0, 0.0));
+        let c = net.add_location(Point3::new(2.0, 0.0, 0.0));
+
+        net.connect_directed(a, b).unwrap();
+        net.connect_directed(a, c).unwrap();
+
+        assert_eq!(net.degree(a), Some(2));
+        assert_eq!(net.degree(b), Some(0));
+    }
+
+    #[test]
+    fn degree_is_none_for_invalid_location() {
+        let net = SpaceNetwork::new();
+        assert_eq!(net.degree(LocationId(7)), None);
+    }
+
+    #[test]
+    fn constrained_route_respects_max_hop_range() {
+        let mut net = SpaceNetwork::new();
+        let a = net.add_location(Point3::new(0.0, 0.0, 0.0));
+        let b = net.add_location(Point3::new(10.0, 0.0, 0.0)); // direct jump = 10, too far
+        let c = net.add_location(Point3::new(5.0, 0.0, 0.0)); // two shorter jumps
+
+        net.connect_bidirectional(a, b).unwrap();
+        net.connect_bidirectional(a, c).unwrap();
+        net.connect_bidirectional(c, b).unwrap();
+
+        let route = net
+            .constrained_route(a, b, 6.0, None)
+            .expect("route within jump range should exist");
+        assert_eq!(route.locations, vec![a, c, b]);
+        assert!(approx_eq(route.total_distance, 10.0));
+    }
+
+    #[test]
+    fn constrained_route_is_none_when_jump_range_too_small() {
+        let mut net = SpaceNetwork::new();
+        let a = net.add_location(Point3::new(0.0, 0.0, 0.0));
+        let b = net.add_location(Point3::new(10.0, 0.0, 0.0));
+        // Only a direct, too-long edge exists.
+        net.connect_bidirectional(a, b).unwrap();
+
+        assert!(net.constrained_route(a, b, 6.0, None).is_none());
+    }
+
+    #[test]
+    fn constrained_route_respects_hop_budget() {
+        let mut net = SpaceNetwork::new();
+        let a = net.add_location(Point3::new(0.0, 0.0, 0.0));
+        let b = net.add_location(Point3::new(3.0, 0.0, 0.0));
+        let c = net.add_location(Point3::new(1.0, 0.0, 0.0));
+        let d = net.add_location(Point3::new(2.0, 0.0, 0.0));
+
+        net.connect_bidirectional(a, b).unwrap(); // direct, 1 hop, distance 3
+        net.connect_bidirectional(a, c).unwrap();
+        net.connect_bidirectional(c, d).unwrap();
+        net.connect_bidirectional(d, b).unwrap(); // 3 hops, distance 3 too
+
+        // Plenty of jump range, but only one hop allowed: must take the
+        // direct edge even though the other path ties on distance.
+        let route = net
+            .constrained_route(a, b, 100.0, Some(1))
+            .expect("direct route should exist");
+        assert_eq!(route.locations, vec![a, b]);
+
+        // No route at all fits within zero hops (start != goal).
+        assert!(net.constrained_route(a, b, 100.0, Some(0)).is_none());
+    }
+
+    #[test]
+    fn constrained_route_same_start_and_goal_is_singleton() {
+        let mut net = SpaceNetwork::new();
+        let a = net.add_location(Point3::new(0.0, 0.0, 0.0));
+
+        let route = net
+            .constrained_route(a, a, 1.0, Some(0))
+            .expect("route should exist");
+        assert_eq!(route.locations, vec![a]);
+        assert!(approx_eq(route.total_distance, 0.0));
+    }
 }
@@ -0,0 +1,151 @@
+//! A minimal 3D k-d tree used to answer range and nearest-neighbor queries
+//! over [`Point3`](crate::Point3) coordinates without falling back to an
+//! O(n^2) scan of every location.
+//!
+//! This is intentionally small and internal: it only supports bulk-loading
+//! from a fixed set of points (rebuilt whenever the network's locations
+//! change) plus radius and k-nearest queries.
+
+use crate::{LocationId, Point3};
+
+struct KdNode {
+    id: LocationId,
+    point: Point3,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+/// A k-d tree over `(LocationId, Point3)` pairs, bulk-loaded once and
+/// queried many times.
+pub(crate) struct KdTree {
+    nodes: Vec<KdNode>,
+    root: Option<usize>,
+}
+
+impl KdTree {
+    /// Build a balanced k-d tree from `points` via recursive median splits.
+    pub(crate) fn build(points: &[(LocationId, Point3)]) -> Self {
+        let mut nodes = Vec::with_capacity(points.len());
+        let mut items: Vec<(LocationId, Point3)> = points.to_vec();
+        let root = Self::build_recursive(&mut items, 0, &mut nodes);
+        Self { nodes, root }
+    }
+
+    fn build_recursive(
+        items: &mut [(LocationId, Point3)],
+        depth: usize,
+        nodes: &mut Vec<KdNode>,
+    ) -> Option<usize> {
+        if items.is_empty() {
+            return None;
+        }
+
+        let axis = depth % 3;
+        items.sort_by(|a, b| axis_value(&a.1, axis).partial_cmp(&axis_value(&b.1, axis)).unwrap());
+
+        let mid = items.len() / 2;
+        let (left_items, rest) = items.split_at_mut(mid);
+        let ((id, point), right_items) = rest.split_first_mut().unwrap();
+
+        let left = Self::build_recursive(left_items, depth + 1, nodes);
+        let right = Self::build_recursive(right_items, depth + 1, nodes);
+
+        nodes.push(KdNode {
+            id: *id,
+            point: *point,
+            left,
+            right,
+        });
+        Some(nodes.len() - 1)
+    }
+
+    /// All points within `range` (inclusive) of `origin`, excluding `origin`
+    /// itself if it happens to be one of the indexed points.
+    pub(crate) fn within_range(&self, origin: Point3, range: f64, exclude: LocationId) -> Vec<LocationId> {
+        let mut found = Vec::new();
+        self.range_recursive(self.root, origin, range, exclude, 0, &mut found);
+        found
+    }
+
+    fn range_recursive(
+        &self,
+        node: Option<usize>,
+        origin: Point3,
+        range: f64,
+        exclude: LocationId,
+        depth: usize,
+        found: &mut Vec<LocationId>,
+    ) {
+        let Some(idx) = node else { return };
+        let n = &self.nodes[idx];
+
+        if n.id != exclude && origin.distance_to(&n.point) <= range {
+            found.push(n.id);
+        }
+
+        let axis = depth % 3;
+        let diff = axis_value(&origin, axis) - axis_value(&n.point, axis);
+
+        let (near, far) = if diff <= 0.0 { (n.left, n.right) } else { (n.right, n.left) };
+        self.range_recursive(near, origin, range, exclude, depth + 1, found);
+        if diff.abs() <= range {
+            self.range_recursive(far, origin, range, exclude, depth + 1, found);
+        }
+    }
+
+    /// The `k` nearest points to `origin`, excluding `exclude`, sorted by
+    /// ascending distance.
+    pub(crate) fn nearest(&self, origin: Point3, k: usize, exclude: LocationId) -> Vec<LocationId> {
+        if k == 0 {
+            return Vec::new();
+        }
+
+        // Best candidates found so far, kept sorted ascending by distance
+        // and capped at length `k`.
+        let mut best: Vec<(f64, LocationId)> = Vec::with_capacity(k);
+        self.nearest_recursive(self.root, origin, k, exclude, 0, &mut best);
+        best.into_iter().map(|(_, id)| id).collect()
+    }
+
+    fn nearest_recursive(
+        &self,
+        node: Option<usize>,
+        origin: Point3,
+        k: usize,
+        exclude: LocationId,
+        depth: usize,
+        best: &mut Vec<(f64, LocationId)>,
+    ) {
+        let Some(idx) = node else { return };
+        let n = &self.nodes[idx];
+
+        if n.id != exclude {
+            let d = origin.distance_to(&n.point);
+            if best.len() < k || d < best.last().unwrap().0 {
+                let pos = best.partition_point(|(bd, _)| *bd < d);
+                best.insert(pos, (d, n.id));
+                best.truncate(k);
+            }
+        }
+
+        let axis = depth % 3;
+        let diff = axis_value(&origin, axis) - axis_value(&n.point, axis);
+
+        let (near, far) = if diff <= 0.0 { (n.left, n.right) } else { (n.right, n.left) };
+        self.nearest_recursive(near, origin, k, exclude, depth + 1, best);
+
+        // Only descend into the far side if it could still contain a closer
+        // point than our current worst kept candidate.
+        if best.len() < k || diff.abs() < best.last().unwrap().0 {
+            self.nearest_recursive(far, origin, k, exclude, depth + 1, best);
+        }
+    }
+}
+
+fn axis_value(p: &Point3, axis: usize) -> f64 {
+    match axis {
+        0 => p.x,
+        1 => p.y,
+        _ => p.z,
+    }
+}